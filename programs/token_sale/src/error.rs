@@ -0,0 +1,51 @@
+use solana_program::program_error::ProgramError;
+use thiserror::Error;
+
+/// Errors raised by the Token Sale program
+#[derive(Error, Clone, Debug, Eq, PartialEq)]
+pub enum TokenSaleError {
+    #[error("account data length does not match the expected layout")]
+    InvalidAccountDataLength,
+
+    #[error("account does not match the expected PDA seeds")]
+    UnexpectedPDASeeds,
+
+    #[error("account must be non-executable")]
+    MustBeNonExecutable,
+
+    #[error("sale authority must sign the instruction")]
+    SaleAuthorityNotSigner,
+
+    #[error("buyer must sign the instruction")]
+    BuyerNotSigner,
+
+    #[error("mint authority does not match the sale authority")]
+    MintAndSaleAuthorityMismatch,
+
+    #[error("buyer is not part of the whitelist")]
+    InvalidWhitelistProof,
+
+    #[error("token program must be either the legacy SPL Token program or Token-2022, and match the sale's")]
+    UnsupportedTokenProgram,
+
+    #[error("mint or vault carries an extension this program does not support")]
+    UnsupportedMintExtension,
+
+    #[error("vault is not the mint's token account")]
+    IncorrectVaultMint,
+
+    #[error("vault is not owned by the token_base PDA")]
+    IncorrectVaultOwner,
+
+    #[error("purchase would exceed the buyer's purchase limit")]
+    PurchaseLimitExceeded,
+
+    #[error("sale_authority account does not match token_base.sale_authority")]
+    IncorrectSaleAuthority,
+}
+
+impl From<TokenSaleError> for ProgramError {
+    fn from(e: TokenSaleError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
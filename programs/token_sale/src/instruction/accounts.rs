@@ -0,0 +1,101 @@
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    program_error::ProgramError,
+};
+
+/// Parses a flat list of [`AccountInfo`]s into a typed accounts struct.
+pub trait Accounts<'a>: Sized {
+    fn try_accounts(accounts: &'a [AccountInfo<'a>]) -> Result<Self, ProgramError>;
+}
+
+/// A typed view over the accounts passed to an instruction.
+pub struct Context<'a, T: Accounts<'a>> {
+    pub accounts: T,
+    pub remaining_accounts: &'a [AccountInfo<'a>],
+}
+
+impl<'a, T: Accounts<'a>> Context<'a, T> {
+    pub fn parse(accounts: &'a [AccountInfo<'a>]) -> Result<Self, ProgramError> {
+        Ok(Self {
+            accounts: T::try_accounts(accounts)?,
+            remaining_accounts: &[],
+        })
+    }
+}
+
+/// Accounts
+/// 0. `[WRITE]`    `Token Base` config account, PDA generated offchain
+/// 1. `[]`         `Mint` account
+/// 2. `[WRITE]`    `Vault` account, the associated token account of `token_base` for `mint`
+/// 3. `[SIGNER]`   `Sale Authority` account
+/// 4. `[]`         `Token Program`, either the legacy token program or Token-2022
+/// 5. `[]`         `Associated Token Program`
+/// 6. `[]`         `Rent` sysvar
+/// 7. `[]`         `System Program`
+pub struct OpenSaleAccounts<'a> {
+    pub token_base: &'a AccountInfo<'a>,
+    pub mint: &'a AccountInfo<'a>,
+    pub vault: &'a AccountInfo<'a>,
+    pub sale_authority: &'a AccountInfo<'a>,
+    pub token_program: &'a AccountInfo<'a>,
+    pub associated_token_program: &'a AccountInfo<'a>,
+    pub rent_sysvar: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+}
+
+impl<'a> Accounts<'a> for OpenSaleAccounts<'a> {
+    fn try_accounts(accounts: &'a [AccountInfo<'a>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+
+        Ok(Self {
+            token_base: next_account_info(accounts_iter)?,
+            mint: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            sale_authority: next_account_info(accounts_iter)?,
+            token_program: next_account_info(accounts_iter)?,
+            associated_token_program: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        })
+    }
+}
+
+/// Accounts
+/// 0. `[SIGNER]`   `Buyer` account
+/// 1. `[]`         `Token Base` config account
+/// 2. `[WRITE]`    `Purchase Receipt` account, PDA created lazily on a buyer's first purchase
+/// 3. `[WRITE]`    `Vault` account, holds the tokens being sold
+/// 4. `[WRITE]`    `Buyer Token Account`, destination for the purchased tokens
+/// 5. `[WRITE]`    `Sale Authority` account, receives payment at `token_base.price`
+/// 6. `[]`         `Token Program`
+/// 7. `[]`         `Rent` sysvar
+/// 8. `[]`         `System Program`
+pub struct BuyAccounts<'a> {
+    pub buyer: &'a AccountInfo<'a>,
+    pub token_base: &'a AccountInfo<'a>,
+    pub purchase_receipt: &'a AccountInfo<'a>,
+    pub vault: &'a AccountInfo<'a>,
+    pub buyer_token_account: &'a AccountInfo<'a>,
+    pub sale_authority: &'a AccountInfo<'a>,
+    pub token_program: &'a AccountInfo<'a>,
+    pub rent_sysvar: &'a AccountInfo<'a>,
+    pub system_program: &'a AccountInfo<'a>,
+}
+
+impl<'a> Accounts<'a> for BuyAccounts<'a> {
+    fn try_accounts(accounts: &'a [AccountInfo<'a>]) -> Result<Self, ProgramError> {
+        let accounts_iter = &mut accounts.iter();
+
+        Ok(Self {
+            buyer: next_account_info(accounts_iter)?,
+            token_base: next_account_info(accounts_iter)?,
+            purchase_receipt: next_account_info(accounts_iter)?,
+            vault: next_account_info(accounts_iter)?,
+            buyer_token_account: next_account_info(accounts_iter)?,
+            sale_authority: next_account_info(accounts_iter)?,
+            token_program: next_account_info(accounts_iter)?,
+            rent_sysvar: next_account_info(accounts_iter)?,
+            system_program: next_account_info(accounts_iter)?,
+        })
+    }
+}
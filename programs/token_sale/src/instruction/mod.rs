@@ -0,0 +1,32 @@
+pub mod accounts;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_error::ProgramError;
+
+/// Top level instruction set for the Token Sale program.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum TokenSaleInstruction {
+    /// Open a whitelisted token sale. See [`crate::processor::open_sale::process_open_sale`].
+    OpenSale {
+        price: u64,
+        purchase_limit: u64,
+        whitelist_root: [u8; 32],
+    },
+
+    /// Buy tokens from an open sale, proving whitelist membership with a
+    /// Merkle proof. `allocation_override` must match the per-buyer cap
+    /// encoded in the buyer's leaf, if any, allowing privileged buyers to
+    /// exceed `default_purchase_limit`. See
+    /// [`crate::processor::buy::process_buy`].
+    Buy {
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+        allocation_override: Option<u64>,
+    },
+}
+
+impl TokenSaleInstruction {
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(data).map_err(|_| ProgramError::InvalidInstructionData)
+    }
+}
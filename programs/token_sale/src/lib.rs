@@ -0,0 +1,58 @@
+pub mod error;
+pub mod instruction;
+pub mod merkle;
+pub mod processor;
+pub mod state;
+pub mod token;
+
+use instruction::TokenSaleInstruction;
+use solana_program::{
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey,
+};
+
+solana_program::declare_id!("WTSaLEkquirapasWhitelistedTokenSa1e111111111");
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    match TokenSaleInstruction::unpack(instruction_data)? {
+        TokenSaleInstruction::OpenSale {
+            price,
+            purchase_limit,
+            whitelist_root,
+        } => processor::open_sale::process_open_sale(
+            program_id,
+            instruction::accounts::Context::parse(accounts)?,
+            price,
+            purchase_limit,
+            whitelist_root,
+        ),
+        TokenSaleInstruction::Buy {
+            amount,
+            proof,
+            allocation_override,
+        } => processor::buy::process_buy(
+            program_id,
+            instruction::accounts::Context::parse(accounts)?,
+            amount,
+            proof,
+            allocation_override,
+        ),
+    }
+}
+
+/// Fails fast with `$err` (converted into a [`solana_program::program_error::ProgramError`])
+/// when `$cond` does not hold, logging which `$context` account/value tripped it.
+#[macro_export]
+macro_rules! require {
+    ($cond:expr, $err:expr, $context:expr) => {
+        if !($cond) {
+            solana_program::msg!("[{}] constraint violated: {}", $context, stringify!($err));
+            return Err($err.into());
+        }
+    };
+}
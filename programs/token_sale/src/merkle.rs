@@ -0,0 +1,86 @@
+use solana_program::keccak::{hashv, Hash};
+use solana_program::pubkey::Pubkey;
+
+/// Computes the whitelist leaf for a buyer with no per-buyer allocation override.
+pub fn buyer_leaf(buyer: &Pubkey) -> Hash {
+    hashv(&[buyer.as_ref()])
+}
+
+/// Computes the whitelist leaf for a buyer carrying a per-buyer allocation
+/// override, letting privileged buyers exceed `default_purchase_limit`
+/// without a separate instruction.
+pub fn buyer_leaf_with_allocation(buyer: &Pubkey, allocation: u64) -> Hash {
+    hashv(&[buyer.as_ref(), &allocation.to_le_bytes()])
+}
+
+/// Folds `proof` into `leaf` using the standard sorted-pair keccak scheme so
+/// roots built off-chain with common JS tooling (e.g. OpenZeppelin's
+/// `MerkleTree.js`) verify unchanged on-chain.
+pub fn verify(leaf: Hash, proof: &[[u8; 32]], root: [u8; 32]) -> bool {
+    let mut computed = leaf.to_bytes();
+    for node in proof {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+    computed == root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted_pair_hash(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    #[test]
+    fn verifies_a_two_leaf_tree() {
+        let buyer_a = Pubkey::new_unique();
+        let buyer_b = Pubkey::new_unique();
+
+        let leaf_a = buyer_leaf(&buyer_a);
+        let leaf_b = buyer_leaf(&buyer_b);
+        let root = sorted_pair_hash(leaf_a.to_bytes(), leaf_b.to_bytes());
+
+        assert!(verify(leaf_a, &[leaf_b.to_bytes()], root));
+        assert!(verify(leaf_b, &[leaf_a.to_bytes()], root));
+    }
+
+    #[test]
+    fn rejects_a_buyer_not_in_the_tree() {
+        let buyer_a = Pubkey::new_unique();
+        let buyer_b = Pubkey::new_unique();
+        let outsider = Pubkey::new_unique();
+
+        let leaf_a = buyer_leaf(&buyer_a);
+        let leaf_b = buyer_leaf(&buyer_b);
+        let root = sorted_pair_hash(leaf_a.to_bytes(), leaf_b.to_bytes());
+
+        let outsider_leaf = buyer_leaf(&outsider);
+        assert!(!verify(outsider_leaf, &[leaf_b.to_bytes()], root));
+    }
+
+    #[test]
+    fn allocation_override_changes_the_leaf() {
+        let buyer = Pubkey::new_unique();
+
+        let leaf_no_override = buyer_leaf(&buyer);
+        let leaf_with_override = buyer_leaf_with_allocation(&buyer, 1_000);
+
+        assert_ne!(leaf_no_override.to_bytes(), leaf_with_override.to_bytes());
+
+        let sibling = [7u8; 32];
+        let root = sorted_pair_hash(leaf_with_override.to_bytes(), sibling);
+
+        // The proof only verifies against the leaf it was built for.
+        assert!(verify(leaf_with_override, &[sibling], root));
+        assert!(!verify(leaf_no_override, &[sibling], root));
+    }
+}
@@ -0,0 +1,217 @@
+use crate::error::TokenSaleError;
+use crate::merkle;
+use crate::state::{find_purchase_receipt_pda, PurchaseReceipt, TokenBase};
+use crate::token::TokenProgram;
+use crate::{
+    instruction::accounts::{BuyAccounts, Context},
+    require,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::sysvar::Sysvar;
+use solana_program::{
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+};
+
+/// Buy tokens from an open, whitelisted sale.
+///
+/// Validates the accounts and the supplied Merkle proof, charges the buyer
+/// `amount * token_base.price` lamports, loads or creates the buyer's
+/// [`PurchaseReceipt`] and enforces their purchase limit, then transfers
+/// `amount` tokens out of the sale's vault into the buyer's token account.
+///
+/// Accounts
+/// 0. `[SIGNER]`   `Buyer` account
+/// 1. `[]`         `Token Base` config account
+/// 2. `[WRITE]`    `Purchase Receipt` account, PDA created lazily on a buyer's first purchase
+/// 3. `[WRITE]`    `Vault` account, holds the tokens being sold
+/// 4. `[WRITE]`    `Buyer Token Account`, destination for the purchased tokens
+/// 5. `[WRITE]`    `Sale Authority` account, receives payment at `token_base.price`
+/// 6. `[]`         `Token Program`
+/// 7. `[]`         `Rent` sysvar
+/// 8. `[]`         `System Program`
+///
+/// Instruction Data
+/// - amount: u64,
+/// - proof: Vec<[u8; 32]>,
+/// - allocation_override: Option<u64>,
+pub fn process_buy(
+    program_id: &Pubkey,
+    ctx: Context<BuyAccounts>,
+    amount: u64,
+    proof: Vec<[u8; 32]>,
+    allocation_override: Option<u64>,
+) -> ProgramResult {
+    //---------- Account Validations ----------
+
+    // 0. buyer
+    //
+    // - must be signer
+    let buyer = ctx.accounts.buyer;
+    require!(buyer.is_signer, TokenSaleError::BuyerNotSigner, "buyer");
+
+    // 1. token_base
+    //
+    // - owner is token_sale (this) program
+    let token_base_account = ctx.accounts.token_base;
+    require!(
+        token_base_account.owner == program_id,
+        ProgramError::InvalidAccountOwner,
+        "token_base"
+    );
+    let token_base_data = token_base_account.try_borrow_data()?;
+    let token_base = TokenBase::try_from_slice(&token_base_data)?;
+
+    // 2. purchase_receipt
+    //
+    // - receipt seeds must be ["receipt", token_base, buyer]
+    let (receipt_pda, receipt_bump) =
+        find_purchase_receipt_pda(program_id, token_base_account.key, buyer.key);
+    let purchase_receipt = ctx.accounts.purchase_receipt;
+    require!(
+        *purchase_receipt.key == receipt_pda,
+        TokenSaleError::UnexpectedPDASeeds,
+        "purchase_receipt"
+    );
+
+    // 3. sale_authority
+    //
+    // - must match token_base.sale_authority
+    require!(
+        *ctx.accounts.sale_authority.key == token_base.sale_authority,
+        TokenSaleError::IncorrectSaleAuthority,
+        "sale_authority"
+    );
+
+    //---------- Data Validations (if any) ----------
+
+    // - buyer must be part of the whitelist committed to in token_base.whitelist_root
+    let leaf = match allocation_override {
+        Some(allocation) => merkle::buyer_leaf_with_allocation(buyer.key, allocation),
+        None => merkle::buyer_leaf(buyer.key),
+    };
+    require!(
+        merkle::verify(leaf, &proof, token_base.whitelist_root),
+        TokenSaleError::InvalidWhitelistProof,
+        "proof"
+    );
+
+    // - token_program must match the one the sale was opened under
+    require!(
+        *ctx.accounts.token_program.key == token_base.token_program.id(),
+        TokenSaleError::UnsupportedTokenProgram,
+        "token_program"
+    );
+
+    //---------- Executing Instruction ----------
+
+    // - charge the buyer `amount * token_base.price` lamports
+    let payment_lamports = amount
+        .checked_mul(token_base.price)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    invoke(
+        &system_instruction::transfer(buyer.key, ctx.accounts.sale_authority.key, payment_lamports),
+        &[
+            buyer.clone(),
+            ctx.accounts.sale_authority.clone(),
+            ctx.accounts.system_program.clone(),
+        ],
+    )?;
+
+    // - load-or-create the receipt, then enforce the purchase limit
+    //
+    // Allocate-then-assign, same as token_base in open_sale: a bare
+    // `create_account` would let anyone pre-fund a buyer's deterministic
+    // receipt PDA with 1 lamport and permanently block that buyer from ever
+    // purchasing. Gate on ownership rather than lamports so a pre-funded
+    // account is still topped up and initialized correctly.
+    if *purchase_receipt.owner != *program_id {
+        let rent_sysvar = &Rent::from_account_info(ctx.accounts.rent_sysvar)?;
+        let receipt_seeds: &[&[u8]] = &[
+            b"receipt",
+            token_base_account.key.as_ref(),
+            buyer.key.as_ref(),
+            &[receipt_bump],
+        ];
+
+        let required_lamports = rent_sysvar.minimum_balance(PurchaseReceipt::LEN);
+        let current_lamports = purchase_receipt.lamports();
+        if current_lamports < required_lamports {
+            invoke(
+                &system_instruction::transfer(
+                    buyer.key,
+                    purchase_receipt.key,
+                    required_lamports - current_lamports,
+                ),
+                &[
+                    buyer.clone(),
+                    purchase_receipt.clone(),
+                    ctx.accounts.system_program.clone(),
+                ],
+            )?;
+        }
+
+        invoke_signed(
+            &system_instruction::allocate(purchase_receipt.key, PurchaseReceipt::LEN as u64),
+            &[purchase_receipt.clone()],
+            &[receipt_seeds],
+        )?;
+
+        invoke_signed(
+            &system_instruction::assign(purchase_receipt.key, program_id),
+            &[purchase_receipt.clone()],
+            &[receipt_seeds],
+        )?;
+    }
+
+    let mut receipt_data = purchase_receipt.try_borrow_mut_data()?;
+    let mut receipt = PurchaseReceipt::try_from_slice(&receipt_data)?;
+
+    let purchase_limit = allocation_override.unwrap_or(token_base.default_purchase_limit);
+    let new_total = receipt.checked_new_total(amount, purchase_limit)?;
+
+    receipt.amount = new_total;
+    receipt.bump = receipt_bump;
+    receipt.serialize(&mut &mut receipt_data[..])?;
+    drop(receipt_data);
+
+    let transfer_ix = match token_base.token_program {
+        TokenProgram::Tokenkeg => spl_token::instruction::transfer(
+            ctx.accounts.token_program.key,
+            ctx.accounts.vault.key,
+            ctx.accounts.buyer_token_account.key,
+            token_base_account.key,
+            &[],
+            amount,
+        )?,
+        TokenProgram::Token2022 => spl_token_2022::instruction::transfer(
+            ctx.accounts.token_program.key,
+            ctx.accounts.vault.key,
+            ctx.accounts.buyer_token_account.key,
+            token_base_account.key,
+            &[],
+            amount,
+        )?,
+    };
+
+    invoke_signed(
+        &transfer_ix,
+        &[
+            ctx.accounts.vault.clone(),
+            ctx.accounts.buyer_token_account.clone(),
+            token_base_account.clone(),
+        ],
+        &[&[
+            b"token_base",
+            token_base.sale_authority.as_ref(),
+            token_base.mint.as_ref(),
+            &[token_base.bump],
+        ]],
+    )?;
+
+    Ok(())
+}
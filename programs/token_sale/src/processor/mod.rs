@@ -0,0 +1,2 @@
+pub mod buy;
+pub mod open_sale;
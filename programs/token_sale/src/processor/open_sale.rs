@@ -1,17 +1,20 @@
 use crate::error::TokenSaleError;
 use crate::state::{find_token_base_pda, TokenBase};
+use crate::token::{self, TokenProgram};
 use crate::{
     instruction::accounts::{Context, OpenSaleAccounts},
     require,
 };
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::sysvar::Sysvar;
 use solana_program::{
-    entrypoint::ProgramResult, program::invoke_signed, program_error::ProgramError,
-    program_pack::Pack, pubkey::Pubkey, rent::Rent, system_instruction,
-    system_program::ID as SYSTEM_PROGRAM_ID,
+    entrypoint::ProgramResult,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
 };
-use spl_token::{error::TokenError, state::Mint};
 
 /// Open a Token Sale with the given config
 ///
@@ -21,8 +24,12 @@ use spl_token::{error::TokenError, state::Mint};
 /// Accounts
 /// 0. `[WRITE]`    `Token Base` config account, PDA generated offchain
 /// 1. `[]`         `Mint` account
-/// 1. `[]`         `Vault` account
-/// 2. `[SIGNER]`   `Sale Authority` account
+/// 2. `[WRITE]`    `Vault` account
+/// 3. `[SIGNER]`   `Sale Authority` account
+/// 4. `[]`         `Token Program`, either the legacy token program or Token-2022
+/// 5. `[]`         `Associated Token Program`
+/// 6. `[]`         `Rent` sysvar
+/// 7. `[]`         `System Program`
 ///
 /// Instruction Data
 /// - price: u64,
@@ -44,32 +51,53 @@ pub fn process_open_sale(
     // - account is uninitialized
     // - token_base seeds must be ["token_base", pubkey(mint)]
 
-    // NOTE: Not ideal but good enough to reach submission
     // inititalize token_base
+    //
+    // Allocate-then-assign instead of a single `create_account`: the latter
+    // fails outright if anyone has already sent the PDA lamports, which lets
+    // a griefer permanently block this (sale_authority, mint) pair from ever
+    // opening a sale. Topping up to rent-exemption first makes initialization
+    // idempotent against pre-funding.
     let rent_sysvar = &Rent::from_account_info(ctx.accounts.rent_sysvar)?;
     let (token_base_pda, token_base_bump) = find_token_base_pda(
         program_id,
         ctx.accounts.sale_authority.key,
         ctx.accounts.mint.key,
     );
+    let token_base_seeds: &[&[u8]] = &[
+        b"token_base",
+        ctx.accounts.sale_authority.key.as_ref(),
+        ctx.accounts.mint.key.as_ref(),
+        &[token_base_bump],
+    ];
+
+    let required_lamports = rent_sysvar.minimum_balance(TokenBase::LEN);
+    let current_lamports = ctx.accounts.token_base.lamports();
+    if current_lamports < required_lamports {
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.sale_authority.key,
+                ctx.accounts.token_base.key,
+                required_lamports - current_lamports,
+            ),
+            &[
+                ctx.accounts.sale_authority.clone(),
+                ctx.accounts.token_base.clone(),
+                ctx.accounts.system_program.clone(),
+            ],
+        )?;
+    }
+
     invoke_signed(
-        &system_instruction::create_account(
-            ctx.accounts.sale_authority.key,
-            ctx.accounts.token_base.key,
-            rent_sysvar.minimum_balance(TokenBase::LEN),
-            TokenBase::LEN as u64,
-            program_id,
-        ),
-        &[
-            ctx.accounts.sale_authority.clone(),
-            ctx.accounts.token_base.clone(),
-        ],
-        &[&[
-            b"token_base",
-            ctx.accounts.sale_authority.key.as_ref(),
-            ctx.accounts.mint.key.as_ref(),
-            &[token_base_bump],
-        ]],
+        &system_instruction::allocate(ctx.accounts.token_base.key, TokenBase::LEN as u64),
+        &[ctx.accounts.token_base.clone()],
+        &[token_base_seeds],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(ctx.accounts.token_base.key, program_id),
+        &[ctx.accounts.token_base.clone()],
+        &[token_base_seeds],
     )?;
 
     // - owner is token_sale (this) program
@@ -102,31 +130,41 @@ pub fn process_open_sale(
         "token_base"
     );
 
+    // Drop the borrow before any CPI that passes token_base as an account
+    // (the vault ATA creation below does) -- invoke/invoke_signed checks
+    // every passed account's RefCell borrow state up front and fails with
+    // `AccountBorrowFailed` if it's still held here.
+    drop(token_base_data);
+
     // 1. mint
     //
     // - is_initialized is true
     // - mint_authority is token_base sale_authority
+    let token_program = TokenProgram::from_program_id(ctx.accounts.token_program.key)?;
+
     let mint = ctx.accounts.mint;
     let mint_data = mint.try_borrow_data()?;
-    let mint_state = Mint::unpack(&mint_data)?;
+    let mint_state = token::unpack_mint(token_program, &mint_data)?;
 
     // - is_initialized is true
-    // require!(
-    //     mint_state.is_initialized,
-    //     TokenError::UninitializedState,
-    //     "mint"
-    // );
+    require!(
+        mint_state.is_initialized,
+        ProgramError::UninitializedAccount,
+        "mint"
+    );
 
     // - mint_authority is token_base sale_authority
-    // require!(
-    //     mint_state.mint_authority.unwrap() == token_base.sale_authority,
-    //     TokenSaleError::MintAndSaleAuthorityMismatch,
-    //     "mint"
-    // );
+    require!(
+        mint_state.mint_authority == Some(*ctx.accounts.sale_authority.key),
+        TokenSaleError::MintAndSaleAuthorityMismatch,
+        "mint"
+    );
 
     // 2. vault
     //
     // - not executable
+    // - is the associated token account of token_base for mint
+    // - initialized as that ATA if it doesn't exist yet
     let vault = ctx.accounts.vault;
 
     // - not executable
@@ -136,6 +174,59 @@ pub fn process_open_sale(
         "vault"
     );
 
+    // - is the associated token account of token_base for mint
+    let expected_vault = spl_associated_token_account::get_associated_token_address_with_program_id(
+        &token_base_pda,
+        mint.key,
+        &token_program.id(),
+    );
+    require!(
+        *vault.key == expected_vault,
+        TokenSaleError::UnexpectedPDASeeds,
+        "vault"
+    );
+
+    // - initialized as that ATA if it doesn't exist yet
+    //
+    // Gate on ownership, not lamports: pre-funding the deterministic vault
+    // address with 1 lamport must not be able to skip ATA creation (the ATA
+    // program's own `create_associated_token_account` already tops up
+    // rent-exemption and allocates/assigns correctly for a pre-funded
+    // account, so there's nothing to gain from checking lamports here).
+    if *vault.owner != token_program.id() {
+        invoke(
+            &spl_associated_token_account::instruction::create_associated_token_account(
+                ctx.accounts.sale_authority.key,
+                &token_base_pda,
+                mint.key,
+                &token_program.id(),
+            ),
+            &[
+                ctx.accounts.sale_authority.clone(),
+                vault.clone(),
+                ctx.accounts.token_base.clone(),
+                mint.clone(),
+                ctx.accounts.system_program.clone(),
+                ctx.accounts.token_program.clone(),
+                ctx.accounts.associated_token_program.clone(),
+            ],
+        )?;
+    }
+
+    let vault_data = vault.try_borrow_data()?;
+    let vault_state = token::unpack_token_account(token_program, &vault_data)?;
+    require!(
+        vault_state.mint == *mint.key,
+        TokenSaleError::IncorrectVaultMint,
+        "vault"
+    );
+    require!(
+        vault_state.owner == token_base_pda,
+        TokenSaleError::IncorrectVaultOwner,
+        "vault"
+    );
+    drop(vault_data);
+
     // 3. sale_authority
     //
     // - not executable
@@ -167,6 +258,10 @@ pub fn process_open_sale(
     token_base.price = price;
     token_base.default_purchase_limit = purchase_limit;
     token_base.bump = token_base_bump; // store canonical bump
+    token_base.token_program = token_program;
+
+    let mut token_base_data = ctx.accounts.token_base.try_borrow_mut_data()?;
+    token_base.serialize(&mut &mut token_base_data[..])?;
 
     Ok(())
 }
@@ -0,0 +1,117 @@
+use crate::token::TokenProgram;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+/// On-chain config for a single whitelisted token sale.
+///
+/// Seeds: `["token_base", sale_authority, mint]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenBase {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub sale_authority: Pubkey,
+    pub whitelist_root: [u8; 32],
+    pub price: u64,
+    pub default_purchase_limit: u64,
+    pub bump: u8,
+    pub token_program: TokenProgram,
+}
+
+impl TokenBase {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 1 + 1;
+
+    /// An account is considered uninitialized until `open_sale` assigns it
+    /// a `sale_authority`.
+    pub fn is_uninitialized(&self) -> bool {
+        self.sale_authority == Pubkey::default()
+    }
+}
+
+/// Derives the [`TokenBase`] PDA for a given `(sale_authority, mint)` pair.
+pub fn find_token_base_pda(
+    program_id: &Pubkey,
+    sale_authority: &Pubkey,
+    mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"token_base", sale_authority.as_ref(), mint.as_ref()],
+        program_id,
+    )
+}
+
+/// Tracks how much of a sale's tokens a given buyer has purchased so far,
+/// so `default_purchase_limit` (or a buyer's allocation override) can be
+/// enforced across multiple purchase transactions.
+///
+/// Seeds: `["receipt", token_base, buyer]`
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PurchaseReceipt {
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl PurchaseReceipt {
+    pub const LEN: usize = 8 + 1;
+
+    /// Computes the receipt's new cumulative amount after purchasing
+    /// `requested` more tokens, rejecting overflow and limit violations
+    /// before any state is mutated.
+    pub fn checked_new_total(
+        &self,
+        requested: u64,
+        limit: u64,
+    ) -> Result<u64, ProgramError> {
+        let new_total = self
+            .amount
+            .checked_add(requested)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if new_total > limit {
+            return Err(crate::error::TokenSaleError::PurchaseLimitExceeded.into());
+        }
+        Ok(new_total)
+    }
+}
+
+/// Derives the [`PurchaseReceipt`] PDA for a given `(token_base, buyer)` pair.
+pub fn find_purchase_receipt_pda(
+    program_id: &Pubkey,
+    token_base: &Pubkey,
+    buyer: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"receipt", token_base.as_ref(), buyer.as_ref()],
+        program_id,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_a_purchase_within_the_limit() {
+        let receipt = PurchaseReceipt {
+            amount: 40,
+            bump: 255,
+        };
+        assert_eq!(receipt.checked_new_total(10, 50).unwrap(), 50);
+    }
+
+    #[test]
+    fn rejects_a_purchase_that_exceeds_the_limit() {
+        let receipt = PurchaseReceipt {
+            amount: 40,
+            bump: 255,
+        };
+        assert!(receipt.checked_new_total(11, 50).is_err());
+    }
+
+    #[test]
+    fn rejects_overflow_even_under_a_generous_limit() {
+        let receipt = PurchaseReceipt {
+            amount: u64::MAX - 1,
+            bump: 255,
+        };
+        assert!(receipt.checked_new_total(10, u64::MAX).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! Thin abstraction over the two SPL token programs the sale program
+//! accepts, so the processor can unpack mints/vaults without caring
+//! whether they live under the legacy token program or Token-2022.
+
+use crate::error::TokenSaleError;
+use crate::require;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, program_pack::Pack, pubkey::Pubkey};
+use spl_token::state::{Account as TokenkegAccount, Mint as TokenkegMint};
+use spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::{Account as Token2022Account, Mint as Token2022Mint},
+};
+
+/// Extensions that let the mint/token-account authority rug the sale or
+/// silently break accounting (fee-on-transfer, forced freezes, a delegate
+/// that can move tokens out from under the vault, etc). Mints carrying any
+/// of these are rejected rather than risk mis-accounting the sale.
+const DISALLOWED_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::PermanentDelegate,
+    ExtensionType::ConfidentialTransferMint,
+    ExtensionType::DefaultAccountState,
+    ExtensionType::NonTransferable,
+];
+
+/// The account-level counterparts of [`DISALLOWED_MINT_EXTENSIONS`] --
+/// `get_extension_types()` on a token account yields account extension
+/// types, not mint ones, so these need their own list. `CpiGuard` and
+/// `MemoTransfer` are included because they'd make this program's own CPI
+/// transfers fail outright.
+const DISALLOWED_TOKEN_ACCOUNT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::TransferFeeAmount,
+    ExtensionType::NonTransferableAccount,
+    ExtensionType::ConfidentialTransferAccount,
+    ExtensionType::CpiGuard,
+    ExtensionType::MemoTransfer,
+];
+
+/// Which SPL token program a sale's mint and vault were created under.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TokenProgram {
+    #[default]
+    Tokenkeg,
+    Token2022,
+}
+
+impl TokenProgram {
+    pub fn from_program_id(program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if *program_id == spl_token::ID {
+            Ok(Self::Tokenkeg)
+        } else if *program_id == spl_token_2022::ID {
+            Ok(Self::Token2022)
+        } else {
+            Err(TokenSaleError::UnsupportedTokenProgram.into())
+        }
+    }
+
+    pub fn id(&self) -> Pubkey {
+        match self {
+            Self::Tokenkeg => spl_token::ID,
+            Self::Token2022 => spl_token_2022::ID,
+        }
+    }
+}
+
+/// A token-program-agnostic view over the fields `open_sale`/`buy` care about.
+pub struct UnpackedMint {
+    pub is_initialized: bool,
+    pub mint_authority: Option<Pubkey>,
+}
+
+/// Unpacks `mint_data` through `token_program`. For Token-2022, `mint_data`
+/// may be longer than the base [`Token2022Mint`] layout (the base struct is
+/// a prefix of the account, followed by a TLV extension region) -- extensions
+/// outside [`DISALLOWED_MINT_EXTENSIONS`] are tolerated.
+pub fn unpack_mint(
+    token_program: TokenProgram,
+    mint_data: &[u8],
+) -> Result<UnpackedMint, ProgramError> {
+    match token_program {
+        TokenProgram::Tokenkeg => {
+            let mint = TokenkegMint::unpack(mint_data)?;
+            Ok(UnpackedMint {
+                is_initialized: mint.is_initialized,
+                mint_authority: mint.mint_authority.into(),
+            })
+        }
+        TokenProgram::Token2022 => {
+            let mint = StateWithExtensions::<Token2022Mint>::unpack(mint_data)?;
+            for extension in mint.get_extension_types()? {
+                require!(
+                    !DISALLOWED_MINT_EXTENSIONS.contains(&extension),
+                    TokenSaleError::UnsupportedMintExtension,
+                    "mint"
+                );
+            }
+            Ok(UnpackedMint {
+                is_initialized: mint.base.is_initialized,
+                mint_authority: mint.base.mint_authority.into(),
+            })
+        }
+    }
+}
+
+/// A token-program-agnostic view over a token account's `mint`/`owner`.
+pub struct UnpackedTokenAccount {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// Unpacks a vault (or buyer token account) through `token_program`, rejecting
+/// [`DISALLOWED_TOKEN_ACCOUNT_EXTENSIONS`] the same way [`unpack_mint`] rejects
+/// [`DISALLOWED_MINT_EXTENSIONS`].
+pub fn unpack_token_account(
+    token_program: TokenProgram,
+    account_data: &[u8],
+) -> Result<UnpackedTokenAccount, ProgramError> {
+    match token_program {
+        TokenProgram::Tokenkeg => {
+            let account = TokenkegAccount::unpack(account_data)?;
+            Ok(UnpackedTokenAccount {
+                mint: account.mint,
+                owner: account.owner,
+            })
+        }
+        TokenProgram::Token2022 => {
+            let account = StateWithExtensions::<Token2022Account>::unpack(account_data)?;
+            for extension in account.get_extension_types()? {
+                require!(
+                    !DISALLOWED_TOKEN_ACCOUNT_EXTENSIONS.contains(&extension),
+                    TokenSaleError::UnsupportedMintExtension,
+                    "vault"
+                );
+            }
+            Ok(UnpackedTokenAccount {
+                mint: account.base.mint,
+                owner: account.base.owner,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::program_option::COption;
+
+    #[test]
+    fn maps_known_program_ids() {
+        assert_eq!(
+            TokenProgram::from_program_id(&spl_token::ID).unwrap(),
+            TokenProgram::Tokenkeg
+        );
+        assert_eq!(
+            TokenProgram::from_program_id(&spl_token_2022::ID).unwrap(),
+            TokenProgram::Token2022
+        );
+        assert!(TokenProgram::from_program_id(&Pubkey::new_unique()).is_err());
+    }
+
+    #[test]
+    fn unpacks_a_legacy_mint() {
+        let authority = Pubkey::new_unique();
+        let mint = TokenkegMint {
+            mint_authority: COption::Some(authority),
+            supply: 0,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        };
+        let mut data = vec![0u8; TokenkegMint::LEN];
+        TokenkegMint::pack(mint, &mut data).unwrap();
+
+        let unpacked = unpack_mint(TokenProgram::Tokenkeg, &data).unwrap();
+        assert!(unpacked.is_initialized);
+        assert_eq!(unpacked.mint_authority, Some(authority));
+    }
+
+    #[test]
+    fn disallowed_extensions_cover_the_accounting_hazards() {
+        // Sanity check on the policy itself: fee-on-transfer and a delegate
+        // able to move tokens out of the vault must never be tolerated.
+        assert!(DISALLOWED_MINT_EXTENSIONS.contains(&ExtensionType::TransferFeeConfig));
+        assert!(DISALLOWED_MINT_EXTENSIONS.contains(&ExtensionType::PermanentDelegate));
+
+        // And the vault's own token account must not carry an extension that
+        // would make this program's CPI transfers fail outright.
+        assert!(DISALLOWED_TOKEN_ACCOUNT_EXTENSIONS.contains(&ExtensionType::CpiGuard));
+        assert!(DISALLOWED_TOKEN_ACCOUNT_EXTENSIONS.contains(&ExtensionType::TransferFeeAmount));
+    }
+}